@@ -0,0 +1,47 @@
+use std::collections::HashMap;
+
+/// A map of named variables (e.g. character stats) that a dice
+/// [`Expression`](crate::Expression) can reference by identifier, such as
+/// `str` in `str+1d20`.
+///
+/// # Example
+///
+/// ```
+/// use one_d_six::Context;
+///
+/// let mut context = Context::new();
+/// context.insert("str", 3);
+///
+/// assert_eq!(context.get("str"), Some(3));
+/// assert_eq!(context.get("dex"), None);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Context {
+    variables: HashMap<String, i64>,
+}
+
+impl Context {
+    /// Creates an empty context.
+    pub fn new() -> Self {
+        Context {
+            variables: HashMap::new(),
+        }
+    }
+
+    /// Sets the value of a named variable, returning its previous value, if
+    /// any.
+    pub fn insert(&mut self, name: impl Into<String>, value: i64) -> Option<i64> {
+        self.variables.insert(name.into(), value)
+    }
+
+    /// Gets the value of a named variable.
+    pub fn get(&self, name: &str) -> Option<i64> {
+        self.variables.get(name).copied()
+    }
+}
+
+impl From<HashMap<String, i64>> for Context {
+    fn from(variables: HashMap<String, i64>) -> Self {
+        Context { variables }
+    }
+}
@@ -2,12 +2,91 @@ use std::fmt;
 use std::ops::Add;
 use std::str::FromStr;
 
+use rand::Rng;
+
 use crate::{
     DiceTotal,
     Die,
+    Error,
     Rollable,
 };
 
+/// A result-shaping modifier that keeps or drops some of a [`Dice`] set's
+/// faces before totaling, e.g. the "keep highest" of advantage or the "drop
+/// lowest" of `4d6` stat generation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeepDropModifier {
+    /// Keep the `n` highest faces, dropping the rest.
+    KeepHighest(usize),
+    /// Keep the `n` lowest faces, dropping the rest.
+    KeepLowest(usize),
+    /// Drop the `n` highest faces, keeping the rest.
+    DropHighest(usize),
+    /// Drop the `n` lowest faces, keeping the rest.
+    DropLowest(usize),
+}
+
+/// For each face, whether it survives a [`KeepDropModifier`].
+fn kept_mask<T: Ord + Copy>(faces: &[T], modifier: KeepDropModifier) -> Vec<bool> {
+    let mut order: Vec<usize> = (0..faces.len()).collect();
+    order.sort_by(|&a, &b| faces[a].cmp(&faces[b]));
+
+    let n = faces.len();
+    let mut kept = vec![true; n];
+    match modifier {
+        KeepDropModifier::KeepHighest(k) => {
+            for &i in order.iter().take(n.saturating_sub(k)) {
+                kept[i] = false;
+            }
+        }
+        KeepDropModifier::KeepLowest(k) => {
+            for &i in order.iter().skip(k) {
+                kept[i] = false;
+            }
+        }
+        KeepDropModifier::DropHighest(k) => {
+            for &i in order.iter().rev().take(k) {
+                kept[i] = false;
+            }
+        }
+        KeepDropModifier::DropLowest(k) => {
+            for &i in order.iter().take(k) {
+                kept[i] = false;
+            }
+        }
+    }
+    kept
+}
+
+/// Parses a `kh`/`kl`/`dh`/`dl` suffix (e.g. `kh3`) into a [`KeepDropModifier`].
+pub(crate) fn parse_modifier_suffix(suffix: &str) -> Result<Option<KeepDropModifier>, Error> {
+    if suffix.is_empty() {
+        return Ok(None);
+    }
+    if suffix.len() < 3 || !suffix.is_ascii() {
+        return Err(Error::ImproperFormat);
+    }
+
+    let (kind, rest) = suffix.split_at(1);
+    let (direction, count) = rest.split_at(1);
+    let count: usize = count.parse().map_err(|_| Error::ImproperFormat)?;
+
+    let modifier = match (kind, direction) {
+        ("k", "h") => KeepDropModifier::KeepHighest(count),
+        ("k", "l") => KeepDropModifier::KeepLowest(count),
+        ("d", "h") => KeepDropModifier::DropHighest(count),
+        ("d", "l") => KeepDropModifier::DropLowest(count),
+        _ => return Err(Error::ImproperFormat),
+    };
+    Ok(Some(modifier))
+}
+
+/// Splits `s` into its leading run of ASCII digits and whatever follows.
+pub(crate) fn split_leading_digits(s: &str) -> (&str, &str) {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    s.split_at(end)
+}
+
 /// A Handful of dice.
 ///
 /// # Examples
@@ -34,8 +113,97 @@ use crate::{
 /// assert!(dice.total() >= 4);
 /// assert!(dice.total() <= 18);
 /// ```
+///
+/// ## Parsing a keep/drop modifier
+///
+/// ```
+/// use one_d_six::Dice;
+///
+/// // Roll 2d20, keeping only the highest (advantage)
+/// let dice: Dice = "2d20kh1".parse().unwrap();
+///
+/// assert!(dice.total() >= 1);
+/// assert!(dice.total() <= 20);
+/// ```
+/// A result-shaping modifier that keeps or drops some of a [`Dice`] set's
+/// faces before totaling, e.g. the "keep highest" of advantage or the "drop
+/// lowest" of `4d6` stat generation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeepDropModifier {
+    /// Keep the `n` highest faces, dropping the rest.
+    KeepHighest(usize),
+    /// Keep the `n` lowest faces, dropping the rest.
+    KeepLowest(usize),
+    /// Drop the `n` highest faces, keeping the rest.
+    DropHighest(usize),
+    /// Drop the `n` lowest faces, keeping the rest.
+    DropLowest(usize),
+}
+
+/// For each face, whether it survives a [`KeepDropModifier`].
+fn kept_mask<T: Ord + Copy>(faces: &[T], modifier: KeepDropModifier) -> Vec<bool> {
+    let mut order: Vec<usize> = (0..faces.len()).collect();
+    order.sort_by(|&a, &b| faces[a].cmp(&faces[b]));
+
+    let n = faces.len();
+    let mut kept = vec![true; n];
+    match modifier {
+        KeepDropModifier::KeepHighest(k) => {
+            for &i in order.iter().take(n.saturating_sub(k)) {
+                kept[i] = false;
+            }
+        }
+        KeepDropModifier::KeepLowest(k) => {
+            for &i in order.iter().skip(k) {
+                kept[i] = false;
+            }
+        }
+        KeepDropModifier::DropHighest(k) => {
+            for &i in order.iter().rev().take(k) {
+                kept[i] = false;
+            }
+        }
+        KeepDropModifier::DropLowest(k) => {
+            for &i in order.iter().take(k) {
+                kept[i] = false;
+            }
+        }
+    }
+    kept
+}
+
+/// Parses a `kh`/`kl`/`dh`/`dl` suffix (e.g. `kh3`) into a [`KeepDropModifier`].
+pub(crate) fn parse_modifier_suffix(suffix: &str) -> Result<Option<KeepDropModifier>, Error> {
+    if suffix.is_empty() {
+        return Ok(None);
+    }
+    if suffix.len() < 3 || !suffix.is_ascii() {
+        return Err(Error::ImproperFormat);
+    }
+
+    let (kind, rest) = suffix.split_at(1);
+    let (direction, count) = rest.split_at(1);
+    let count: usize = count.parse().map_err(|_| Error::ImproperFormat)?;
+
+    let modifier = match (kind, direction) {
+        ("k", "h") => KeepDropModifier::KeepHighest(count),
+        ("k", "l") => KeepDropModifier::KeepLowest(count),
+        ("d", "h") => KeepDropModifier::DropHighest(count),
+        ("d", "l") => KeepDropModifier::DropLowest(count),
+        _ => return Err(Error::ImproperFormat),
+    };
+    Ok(Some(modifier))
+}
+
+/// Splits `s` into its leading run of ASCII digits and whatever follows.
+pub(crate) fn split_leading_digits(s: &str) -> (&str, &str) {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    s.split_at(end)
+}
+
 pub struct Dice<T: Rollable = u32> {
     dice: Vec<Die<T>>,
+    modifier: Option<KeepDropModifier>,
 }
 
 impl<T: Rollable> Add for Dice<T> {
@@ -49,29 +217,35 @@ impl<T: Rollable> Add for Dice<T> {
         for die in other.dice.into_iter() {
             dice.push(die);
         }
-        Dice { dice }
+        Dice {
+            dice,
+            modifier: None,
+        }
     }
 }
 
+fn parse_amount_and_faces<T: FromStr>(
+    s: &str,
+) -> Result<(usize, T, Option<KeepDropModifier>), Error> {
+    let d_index = s.find('d').ok_or(Error::MissingD)?;
+    let amount: usize = s[..d_index].parse().map_err(|_| Error::ImproperFormat)?;
+
+    let rest = &s[d_index + 1..];
+    let (faces_str, suffix) = split_leading_digits(rest);
+    let faces: T = faces_str.parse().map_err(|_| Error::ImproperFormat)?;
+    let modifier = parse_modifier_suffix(suffix)?;
+
+    Ok((amount, faces, modifier))
+}
+
 impl<T: Rollable> FromStr for Dice<T> where T: FromStr {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (dice_amount, dice_faces): (usize, T) = {
-            let mut s = s.split('d');
-            let values = if let (Some(d), Some(f)) = (s.next(), s.next()) {
-                (d.parse(), f.parse())
-            } else {
-                return Err(String::from("Missing 'd'"));
-            };
-
-            if let (Ok(d), Ok(f)) = values {
-                (d, f)
-            } else {
-                return Err(String::from("Improper dice format"));
-            }
-        };
-        Ok(Dice::new(dice_amount, dice_faces))
+        let (dice_amount, dice_faces, modifier) = parse_amount_and_faces(s)?;
+        let mut dice = Dice::new(dice_amount, dice_faces);
+        dice.modifier = modifier;
+        Ok(dice)
     }
 }
 
@@ -79,32 +253,48 @@ impl<T: Rollable> fmt::Display for Dice<T>
 where
     T: DiceTotal<T>,
     T: fmt::Display,
+    T: Ord,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.total())
     }
 }
 
-impl<T: Rollable> fmt::Debug for Dice<T> where T: fmt::Display {
+impl<T: Rollable> fmt::Debug for Dice<T>
+where
+    T: fmt::Display,
+    T: Ord,
+{
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut iter = self.dice.iter();
-        let first = match iter.next() {
-            Some(d) => d,
-            None => return Err(fmt::Error),
+        let faces = self.current_faces();
+        let kept = match self.modifier {
+            Some(modifier) => kept_mask(&faces, modifier),
+            None => vec![true; faces.len()],
         };
-        if let Err(e) = write!(f, "{}", first.current_face()) {
-            return Err(e);
-        }
+        let mut iter = faces.iter().zip(kept.iter());
 
-        for die in iter {
-            if let Err(e) = write!(f, " {}", die.current_face()) {
-                return Err(e);
-            }
+        let (first_face, first_kept) = match iter.next() {
+            Some(pair) => pair,
+            None => return Ok(()),
+        };
+        write_face(f, first_face, *first_kept)?;
+
+        for (face, kept) in iter {
+            write!(f, " ")?;
+            write_face(f, face, *kept)?;
         }
         Ok(())
     }
 }
 
+fn write_face<T: fmt::Display>(f: &mut fmt::Formatter, face: &T, kept: bool) -> fmt::Result {
+    if kept {
+        write!(f, "{}", face)
+    } else {
+        write!(f, "({})", face)
+    }
+}
+
 impl<T: Rollable> Dice<T> {
     /// Creates a new set of dice.
     /// Each die in the set has an initial starting value.
@@ -119,15 +309,60 @@ impl<T: Rollable> Dice<T> {
     /// let dice: Dice = Dice::new(3, 6);
     /// ```
     pub fn new(dice: usize, faces: T) -> Self {
+        Self::new_with(dice, faces, &mut rand::thread_rng())
+    }
+
+    /// Creates a new set of dice, rolled using the given RNG source.
+    /// Each die in the set has an initial starting value.
+    /// Only allows dice of same type. No mixture of d4 and d6.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use one_d_six::Dice;
+    /// use rand::SeedableRng;
+    /// use rand::rngs::StdRng;
+    ///
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// // Creates 3d6 dice collection
+    /// let dice: Dice = Dice::new_with(3, 6, &mut rng);
+    /// ```
+    pub fn new_with<R: Rng>(dice: usize, faces: T, rng: &mut R) -> Self {
         let dice = {
             let mut v: Vec<Die<T>> = Vec::with_capacity(dice);
             for _ in 0..dice {
-                v.push(Die::new(faces));
+                v.push(Die::new_with(faces, rng));
             }
             v
         };
 
-        Dice { dice }
+        Dice {
+            dice,
+            modifier: None,
+        }
+    }
+
+    /// Parses a set of dice from a *1d6* style string, rolled using the
+    /// given RNG source.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use one_d_six::Dice;
+    /// use rand::SeedableRng;
+    /// use rand::rngs::StdRng;
+    ///
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// let dice: Dice = Dice::from_str_with("3d6", &mut rng).unwrap();
+    /// ```
+    pub fn from_str_with<R: Rng>(s: &str, rng: &mut R) -> Result<Self, Error>
+    where
+        T: FromStr,
+    {
+        let (dice_amount, dice_faces, modifier) = parse_amount_and_faces(s)?;
+        let mut dice = Dice::new_with(dice_amount, dice_faces, rng);
+        dice.modifier = modifier;
+        Ok(dice)
     }
 
     /// Creates a set of dice from a `Vec<Die>`.
@@ -154,7 +389,10 @@ impl<T: Rollable> Dice<T> {
     pub fn from(dice: Box<[Die<T>]>) -> Self {
         let dice = dice.into_vec();
 
-        Dice { dice }
+        Dice {
+            dice,
+            modifier: None,
+        }
     }
 
     /// Gets the current face of each die in the dice set.
@@ -190,14 +428,36 @@ impl<T: Rollable> Dice<T> {
     /// }
     /// ```
     pub fn roll_all(&mut self) -> &Self {
-        let iter = self.dice.iter_mut().map(|die| {
-            die.roll();
-        });
-        for _ in iter {}
+        self.roll_all_with(&mut rand::thread_rng())
+    }
+
+    /// Rolls all dice using the given RNG source and returns self.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use one_d_six::Dice;
+    /// use rand::SeedableRng;
+    /// use rand::rngs::StdRng;
+    ///
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// let mut ten_d_4 = Dice::new(10, 4);
+    ///
+    /// for val in ten_d_4.roll_all_with(&mut rng).current_faces().iter() {
+    ///     let val: u32 = *val;
+    ///     assert!(val >= 1);
+    ///     assert!(val <= 4);
+    /// }
+    /// ```
+    pub fn roll_all_with<R: Rng>(&mut self, rng: &mut R) -> &Self {
+        for die in self.dice.iter_mut() {
+            die.roll_with(rng);
+        }
         self
     }
 
-    /// Gets the total of the current faces of the dice.
+    /// Gets the total of the current faces of the dice, honoring any active
+    /// [`KeepDropModifier`] by leaving dropped dice out of the sum.
     ///
     /// # Example
     ///
@@ -212,7 +472,188 @@ impl<T: Rollable> Dice<T> {
     pub fn total(&self) -> T
     where
         T: DiceTotal<T>,
+        T: Ord,
     {
-        T::dice_total(self.current_faces())
+        let faces = self.current_faces();
+        match self.modifier {
+            None => T::dice_total(faces),
+            Some(modifier) => {
+                let kept = kept_mask(&faces, modifier);
+                let kept_faces = faces
+                    .into_iter()
+                    .zip(kept)
+                    .filter(|&(_, kept)| kept)
+                    .map(|(face, _)| face)
+                    .collect();
+                T::dice_total(kept_faces)
+            }
+        }
+    }
+
+    /// Keeps only the `k` highest faces, dropping the rest before
+    /// totaling. The standard advantage mechanic.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use one_d_six::Dice;
+    ///
+    /// // Roll 2d20, keep the highest (advantage)
+    /// let mut dice: Dice = Dice::new(2, 20);
+    /// dice.keep_highest(1);
+    ///
+    /// assert!(dice.total() >= 1);
+    /// assert!(dice.total() <= 20);
+    /// ```
+    pub fn keep_highest(&mut self, k: usize) -> &Self {
+        self.modifier = Some(KeepDropModifier::KeepHighest(k));
+        self
+    }
+
+    /// Keeps only the `k` lowest faces, dropping the rest before totaling.
+    /// The standard disadvantage mechanic.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use one_d_six::Dice;
+    ///
+    /// // Roll 2d20, keep the lowest (disadvantage)
+    /// let mut dice: Dice = Dice::new(2, 20);
+    /// dice.keep_lowest(1);
+    ///
+    /// assert!(dice.total() >= 1);
+    /// assert!(dice.total() <= 20);
+    /// ```
+    pub fn keep_lowest(&mut self, k: usize) -> &Self {
+        self.modifier = Some(KeepDropModifier::KeepLowest(k));
+        self
+    }
+
+    /// Drops the `k` highest faces before totaling.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use one_d_six::Dice;
+    ///
+    /// let mut dice: Dice = Dice::new(4, 6);
+    /// dice.drop_highest(1);
+    ///
+    /// assert!(dice.total() >= 3);
+    /// assert!(dice.total() <= 18);
+    /// ```
+    pub fn drop_highest(&mut self, k: usize) -> &Self {
+        self.modifier = Some(KeepDropModifier::DropHighest(k));
+        self
+    }
+
+    /// Drops the `k` lowest faces before totaling. The standard `4d6` drop
+    /// lowest stat-generation mechanic.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use one_d_six::Dice;
+    ///
+    /// let mut dice: Dice = Dice::new(4, 6);
+    /// dice.drop_lowest(1);
+    ///
+    /// assert!(dice.total() >= 3);
+    /// assert!(dice.total() <= 18);
+    /// ```
+    pub fn drop_lowest(&mut self, k: usize) -> &Self {
+        self.modifier = Some(KeepDropModifier::DropLowest(k));
+        self
+    }
+
+    /// Sets the active [`KeepDropModifier`] directly.
+    pub(crate) fn apply_modifier(&mut self, modifier: KeepDropModifier) -> &Self {
+        self.modifier = Some(modifier);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kept_mask_keeps_highest() {
+        let faces = [3, 1, 4, 1, 5];
+        let kept = kept_mask(&faces, KeepDropModifier::KeepHighest(2));
+
+        assert_eq!(kept, vec![false, false, true, false, true]);
+    }
+
+    #[test]
+    fn kept_mask_keeps_lowest() {
+        let faces = [3, 1, 4, 1, 5];
+        let kept = kept_mask(&faces, KeepDropModifier::KeepLowest(2));
+
+        assert_eq!(kept, vec![false, true, false, true, false]);
+    }
+
+    #[test]
+    fn kept_mask_drops_highest() {
+        let faces = [3, 1, 4, 1, 5];
+        let kept = kept_mask(&faces, KeepDropModifier::DropHighest(1));
+
+        assert_eq!(kept, vec![true, true, true, true, false]);
+    }
+
+    #[test]
+    fn kept_mask_drops_lowest() {
+        let faces = [3, 1, 4, 1, 5];
+        let kept = kept_mask(&faces, KeepDropModifier::DropLowest(1));
+
+        assert_eq!(kept, vec![true, false, true, true, true]);
+    }
+
+    #[test]
+    fn parse_modifier_suffix_parses_each_direction() {
+        assert_eq!(parse_modifier_suffix("").unwrap(), None);
+        assert_eq!(
+            parse_modifier_suffix("kh3").unwrap(),
+            Some(KeepDropModifier::KeepHighest(3))
+        );
+        assert_eq!(
+            parse_modifier_suffix("kl1").unwrap(),
+            Some(KeepDropModifier::KeepLowest(1))
+        );
+        assert_eq!(
+            parse_modifier_suffix("dh2").unwrap(),
+            Some(KeepDropModifier::DropHighest(2))
+        );
+        assert_eq!(
+            parse_modifier_suffix("dl1").unwrap(),
+            Some(KeepDropModifier::DropLowest(1))
+        );
+    }
+
+    #[test]
+    fn parse_modifier_suffix_rejects_malformed_input() {
+        assert!(parse_modifier_suffix("k").is_err());
+        assert!(parse_modifier_suffix("xy1").is_err());
+        assert!(parse_modifier_suffix("khx").is_err());
+    }
+
+    #[test]
+    fn parse_modifier_suffix_rejects_non_ascii_without_panicking() {
+        assert!(parse_modifier_suffix("éx").is_err());
+        assert!(parse_modifier_suffix("ñ1").is_err());
+    }
+
+    #[test]
+    fn keep_highest_respects_modifier_on_total() {
+        for _ in 0..100 {
+            let mut dice: Dice<u32> = Dice::new(2, 20);
+            dice.keep_highest(1);
+
+            let faces = dice.current_faces();
+            let expected = *faces.iter().max().unwrap();
+
+            assert_eq!(dice.total(), expected);
+        }
     }
 }
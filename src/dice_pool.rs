@@ -0,0 +1,338 @@
+use rand::Rng;
+
+use crate::{
+    Dice,
+    Die,
+    Rollable,
+};
+
+/// Caps how many extra dice "again" explosions can add in a single
+/// [`DicePool::count_successes`] call, so a pathological again-value (e.g. 1)
+/// can't loop forever.
+const MAX_EXPLOSIONS: usize = 100;
+
+/// Controls how [`DicePool::count_successes`] treats successes beyond a
+/// simple threshold check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DicePoolQuality {
+    /// A face at or above the success threshold counts once. No explosions,
+    /// no rerolls.
+    Standard,
+    /// A face showing 9 or higher is a success *and* rolls an extra die,
+    /// which can itself explode again.
+    NineAgain,
+    /// A face showing 8 or higher is a success *and* rolls an extra die,
+    /// which can itself explode again.
+    EightAgain,
+    /// A face showing 10 or higher is a success *and* rolls an extra die,
+    /// which can itself explode again.
+    TenAgain,
+    /// Each die that initially fails the success threshold is rerolled
+    /// exactly once.
+    Rote,
+}
+
+/// The outcome of a [`DicePool`] roll, in Chronicles-of-Darkness terms.
+///
+/// There's no separate variant for a "chance die" (the single-d10 roll made
+/// when a pool has zero or negative dice): it isn't a distinct scoring
+/// rule, just a [`DicePool`] of one die, so `DicePool::new(1, 10).count_successes(10, DicePoolQuality::Standard)`
+/// already produces the right [`Botch`](DicePoolOutcome::Botch)/[`Failure`](DicePoolOutcome::Failure)/[`Success`](DicePoolOutcome::Success)
+/// split without new enum cases.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DicePoolOutcome {
+    /// No successes on a one-die (chance die) pool, with that die showing
+    /// the lowest possible face. A multi-die pool can never Botch; a
+    /// zero-success roll there is always a [`Failure`](DicePoolOutcome::Failure),
+    /// even if one of its dice happens to show the lowest face.
+    Botch,
+    /// No successes.
+    Failure,
+    /// At least one success.
+    Success,
+    /// Five or more successes.
+    ExceptionalSuccess,
+}
+
+/// The result of [`DicePool::count_successes`]: how many successes were
+/// rolled, every face that was rolled (including exploded dice), and the
+/// resulting [`DicePoolOutcome`].
+pub struct DicePoolResult<T: Rollable = u32> {
+    successes: usize,
+    faces: Vec<T>,
+}
+
+impl<T: Rollable> DicePoolResult<T> {
+    /// The number of successes rolled.
+    pub fn successes(&self) -> usize {
+        self.successes
+    }
+
+    /// Every face rolled, including dice added by explosions.
+    pub fn faces(&self) -> &[T] {
+        &self.faces
+    }
+
+    /// `true` if 5 or more successes were rolled.
+    pub fn is_exceptional_success(&self) -> bool {
+        self.successes >= 5
+    }
+}
+
+impl<T: Rollable> DicePoolResult<T>
+where
+    T: PartialEq + From<u8>,
+{
+    /// Classifies the roll as a [`DicePoolOutcome`].
+    pub fn outcome(&self) -> DicePoolOutcome {
+        if self.successes >= 5 {
+            DicePoolOutcome::ExceptionalSuccess
+        } else if self.successes > 0 {
+            DicePoolOutcome::Success
+        } else if self.faces.len() == 1 && self.faces[0] == T::from(1) {
+            DicePoolOutcome::Botch
+        } else {
+            DicePoolOutcome::Failure
+        }
+    }
+}
+
+/// A pool of same-sided dice scored by counting successes, rather than
+/// summing faces, as used in Chronicles-of-Darkness-style games.
+///
+/// # Examples
+///
+/// ```
+/// use one_d_six::{DicePool, DicePoolQuality};
+///
+/// // 4 dice, success on 8+, exploding on 9+ ("9-again")
+/// let pool: DicePool = DicePool::new(4, 10);
+/// let result = pool.count_successes(8, DicePoolQuality::NineAgain);
+///
+/// assert!(result.successes() <= result.faces().len());
+/// ```
+pub struct DicePool<T: Rollable = u32> {
+    faces: T,
+    dice: Dice<T>,
+}
+
+impl<T: Rollable> DicePool<T> {
+    /// Creates a pool of `count` dice, each with `faces` sides.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use one_d_six::DicePool;
+    ///
+    /// let pool: DicePool = DicePool::new(5, 10);
+    /// ```
+    pub fn new(count: usize, faces: T) -> Self {
+        DicePool {
+            faces,
+            dice: Dice::new(count, faces),
+        }
+    }
+
+    /// Wraps an already-rolled [`Dice`] set as a pool. `faces` should match
+    /// the number of sides the dice in `dice` were rolled with, since it's
+    /// used when rolling rerolls and explosions.
+    pub fn from_dice(faces: T, dice: Dice<T>) -> Self {
+        DicePool { faces, dice }
+    }
+}
+
+impl<T: Rollable> DicePool<T>
+where
+    T: PartialOrd + From<u8>,
+{
+    /// Scores the pool by counting successes (a face at or above
+    /// `threshold`), applying the explosion/reroll rules of `quality`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use one_d_six::{DicePool, DicePoolQuality};
+    ///
+    /// let pool: DicePool = DicePool::new(8, 10);
+    /// let result = pool.count_successes(8, DicePoolQuality::Rote);
+    ///
+    /// assert!(result.successes() <= result.faces().len());
+    /// ```
+    pub fn count_successes(&self, threshold: T, quality: DicePoolQuality) -> DicePoolResult<T> {
+        self.count_successes_with(threshold, quality, &mut rand::thread_rng())
+    }
+
+    /// Scores the pool by counting successes (a face at or above
+    /// `threshold`), applying the explosion/reroll rules of `quality`, using
+    /// the given RNG source for any rerolls or explosions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use one_d_six::{DicePool, DicePoolQuality};
+    /// use rand::SeedableRng;
+    /// use rand::rngs::StdRng;
+    ///
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// let pool: DicePool = DicePool::new(8, 10);
+    /// let result = pool.count_successes_with(8, DicePoolQuality::Rote, &mut rng);
+    ///
+    /// assert!(result.successes() <= result.faces().len());
+    /// ```
+    pub fn count_successes_with<R: Rng>(
+        &self,
+        threshold: T,
+        quality: DicePoolQuality,
+        rng: &mut R,
+    ) -> DicePoolResult<T> {
+        let again = match quality {
+            DicePoolQuality::NineAgain => Some(T::from(9)),
+            DicePoolQuality::EightAgain => Some(T::from(8)),
+            DicePoolQuality::TenAgain => Some(T::from(10)),
+            DicePoolQuality::Standard | DicePoolQuality::Rote => None,
+        };
+
+        let mut faces = self.dice.current_faces();
+
+        if quality == DicePoolQuality::Rote {
+            for face in faces.iter_mut() {
+                if *face < threshold {
+                    *face = Die::new_with(self.faces, rng).current_face();
+                }
+            }
+        }
+
+        let mut all_faces = faces.clone();
+        let mut successes = faces.iter().filter(|&&face| face >= threshold).count();
+
+        if let Some(again) = again {
+            let mut exploding: Vec<T> = faces.into_iter().filter(|&face| face >= again).collect();
+            let mut explosions = 0;
+
+            while !exploding.is_empty() && explosions < MAX_EXPLOSIONS {
+                let mut next = Vec::new();
+                for _ in exploding {
+                    let face = Die::new_with(self.faces, rng).current_face();
+                    all_faces.push(face);
+                    if face >= threshold {
+                        successes += 1;
+                    }
+                    if face >= again {
+                        next.push(face);
+                    }
+
+                    explosions += 1;
+                    if explosions >= MAX_EXPLOSIONS {
+                        break;
+                    }
+                }
+                exploding = next;
+            }
+        }
+
+        DicePoolResult {
+            successes,
+            faces: all_faces,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn successes_count_faces_at_or_above_threshold() {
+        for _ in 0..100 {
+            let pool: DicePool = DicePool::new(10, 10);
+            let result = pool.count_successes(8, DicePoolQuality::Standard);
+
+            assert_eq!(result.faces().len(), 10);
+            assert_eq!(
+                result.successes(),
+                result.faces().iter().filter(|&&face| face >= 8).count()
+            );
+        }
+    }
+
+    #[test]
+    fn again_explosions_are_also_counted_as_successes() {
+        for _ in 0..100 {
+            let pool: DicePool = DicePool::new(10, 10);
+            let result = pool.count_successes(8, DicePoolQuality::NineAgain);
+
+            assert_eq!(
+                result.successes(),
+                result.faces().iter().filter(|&&face| face >= 8).count()
+            );
+        }
+    }
+
+    #[test]
+    fn rote_rerolls_never_change_the_face_count() {
+        for _ in 0..100 {
+            let pool: DicePool = DicePool::new(10, 10);
+            let result = pool.count_successes(8, DicePoolQuality::Rote);
+
+            // Rote only rerolls failing dice in place; it never explodes, so
+            // the face count can never grow past the original pool size.
+            assert_eq!(result.faces().len(), 10);
+            assert_eq!(
+                result.successes(),
+                result.faces().iter().filter(|&&face| face >= 8).count()
+            );
+        }
+    }
+
+    #[test]
+    fn explosions_are_capped() {
+        for _ in 0..100 {
+            let pool: DicePool = DicePool::new(10, 10);
+            let result = pool.count_successes(8, DicePoolQuality::NineAgain);
+
+            assert!(result.faces().len() <= 10 + MAX_EXPLOSIONS);
+        }
+    }
+
+    #[test]
+    fn outcome_matches_successes_for_a_multi_die_pool() {
+        for _ in 0..100 {
+            let pool: DicePool = DicePool::new(5, 10);
+            let result = pool.count_successes(8, DicePoolQuality::Standard);
+
+            match result.outcome() {
+                DicePoolOutcome::ExceptionalSuccess => assert!(result.successes() >= 5),
+                DicePoolOutcome::Success => {
+                    assert!(result.successes() > 0 && result.successes() < 5)
+                }
+                // A pool with more than one die can't Botch; a zero-success
+                // roll is always a plain Failure, even if one of its dice
+                // shows a 1.
+                DicePoolOutcome::Botch => panic!("a multi-die pool should never Botch"),
+                DicePoolOutcome::Failure => assert_eq!(result.successes(), 0),
+            }
+        }
+    }
+
+    #[test]
+    fn outcome_botches_only_on_a_single_die_showing_the_lowest_face() {
+        for _ in 0..100 {
+            let pool: DicePool = DicePool::new(1, 10);
+            let result = pool.count_successes(8, DicePoolQuality::Standard);
+
+            match result.outcome() {
+                DicePoolOutcome::Botch => {
+                    assert_eq!(result.successes(), 0);
+                    assert_eq!(result.faces(), &[1]);
+                }
+                DicePoolOutcome::Failure => {
+                    assert_eq!(result.successes(), 0);
+                    assert_ne!(result.faces(), &[1]);
+                }
+                DicePoolOutcome::Success | DicePoolOutcome::ExceptionalSuccess => {
+                    assert!(result.successes() > 0)
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use std::ops::Add;
 
+use rand::Rng;
+
 use crate::Rollable;
 
 /// Represents a single die.
@@ -64,9 +66,25 @@ impl<T: Rollable> Die<T> {
     /// let coin = Die::new(2);
     /// ```
     pub fn new(faces: T) -> Self {
+        Self::new_with(faces, &mut rand::thread_rng())
+    }
+    /// Creates a single die with the specified number of faces, rolled
+    /// using the given RNG source.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use one_d_six::Die;
+    /// use rand::SeedableRng;
+    /// use rand::rngs::StdRng;
+    ///
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// let coin: Die = Die::new_with(2, &mut rng);
+    /// ```
+    pub fn new_with<R: Rng>(faces: T, rng: &mut R) -> Self {
         let die = Die {
             faces,
-            current_value: T::roll(faces),
+            current_value: T::roll_with(faces, rng),
         };
         die
     }
@@ -83,7 +101,25 @@ impl<T: Rollable> Die<T> {
     /// assert!(d6.current_face() <= 6);
     /// ```
     pub fn roll(&mut self) -> T {
-        self.current_value = T::roll(self.faces);
+        self.roll_with(&mut rand::thread_rng())
+    }
+    /// Rolls a single die using the given RNG source.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use one_d_six::Die;
+    /// use rand::SeedableRng;
+    /// use rand::rngs::StdRng;
+    ///
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// let mut d6: Die = Die::new(6);
+    ///
+    /// assert!(d6.roll_with(&mut rng) >= 1);
+    /// assert!(d6.current_face() <= 6);
+    /// ```
+    pub fn roll_with<R: Rng>(&mut self, rng: &mut R) -> T {
+        self.current_value = T::roll_with(self.faces, rng);
         self.current_value
     }
     /// Gets the current value of the die.
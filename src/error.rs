@@ -0,0 +1,26 @@
+use std::fmt;
+
+/// Errors produced when parsing or evaluating a dice [`Expression`](crate::Expression)
+/// or [`Dice`](crate::Dice) string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// An identifier in the expression had no matching entry in the
+    /// [`Context`](crate::Context) it was evaluated against.
+    VariableNotFound(String),
+    /// A dice term (e.g. `3d6`) was missing its `d` separator.
+    MissingD,
+    /// The input didn't match a recognized dice, bonus, or variable token.
+    ImproperFormat,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::VariableNotFound(name) => write!(f, "variable not found: {}", name),
+            Error::MissingD => write!(f, "missing 'd'"),
+            Error::ImproperFormat => write!(f, "improper format"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,706 @@
+use std::fmt;
+use std::str::FromStr;
+
+use rand::Rng;
+
+use crate::dice::{
+    parse_modifier_suffix,
+    split_leading_digits,
+};
+use crate::{
+    Context,
+    Dice,
+    Error,
+    KeepDropModifier,
+    Rollable,
+};
+
+/// Converts a rolled face value into a signed total.
+///
+/// Used by [`Expression::roll`] so that unsigned face types (the only types
+/// [`Rollable`] is implemented for today) can still be summed and subtracted
+/// when an expression contains a negative term.
+pub trait IntoTotal {
+    fn into_total(self) -> i64;
+}
+
+impl IntoTotal for u8 {
+    fn into_total(self) -> i64 {
+        self as i64
+    }
+}
+impl IntoTotal for u16 {
+    fn into_total(self) -> i64 {
+        self as i64
+    }
+}
+impl IntoTotal for u32 {
+    fn into_total(self) -> i64 {
+        self as i64
+    }
+}
+impl IntoTotal for u64 {
+    fn into_total(self) -> i64 {
+        self as i64
+    }
+}
+impl IntoTotal for u128 {
+    fn into_total(self) -> i64 {
+        self as i64
+    }
+}
+impl IntoTotal for usize {
+    fn into_total(self) -> i64 {
+        self as i64
+    }
+}
+
+/// Converts a signed total back into a rollable face type. The reverse of
+/// [`IntoTotal`]; used to resolve a variable's value into a flat bonus.
+///
+/// `Self` is unsigned, so a negative `value` is clamped to `0` rather than
+/// wrapping around to a huge positive number, matching how
+/// [`resolve_count`] clamps a negative variable used as a dice count.
+pub trait FromTotal {
+    fn from_total(value: i64) -> Self;
+}
+
+impl FromTotal for u8 {
+    fn from_total(value: i64) -> Self {
+        value.max(0) as u8
+    }
+}
+impl FromTotal for u16 {
+    fn from_total(value: i64) -> Self {
+        value.max(0) as u16
+    }
+}
+impl FromTotal for u32 {
+    fn from_total(value: i64) -> Self {
+        value.max(0) as u32
+    }
+}
+impl FromTotal for u64 {
+    fn from_total(value: i64) -> Self {
+        value.max(0) as u64
+    }
+}
+impl FromTotal for u128 {
+    fn from_total(value: i64) -> Self {
+        value.max(0) as u128
+    }
+}
+impl FromTotal for usize {
+    fn from_total(value: i64) -> Self {
+        value.max(0) as usize
+    }
+}
+
+/// A dice count: either a literal number of dice, or a named variable that
+/// resolves to one via a [`Context`].
+#[derive(Clone, Debug)]
+pub enum CountValue {
+    Literal(usize),
+    Variable(String),
+}
+
+/// A flat bonus value: either a literal, or a named variable that resolves
+/// to one via a [`Context`].
+#[derive(Clone, Debug)]
+pub enum Value<T: Rollable = u32> {
+    Literal(T),
+    Variable(String),
+}
+
+/// A single term of a dice [`Expression`]: either a group of dice to be
+/// rolled or a flat bonus.
+#[derive(Clone, Debug)]
+pub enum Element<T: Rollable = u32> {
+    /// `count` dice, each with `faces` sides, with an optional keep/drop
+    /// modifier (e.g. the `kh3` in `4d6kh3`).
+    Dice(CountValue, T, Option<KeepDropModifier>),
+    /// A flat value added to (or subtracted from) the expression's total.
+    Bonus(Value<T>),
+}
+
+/// An [`Element`] together with the sign it carries in its [`Expression`].
+#[derive(Clone, Debug)]
+pub enum SignedElement<T: Rollable = u32> {
+    Positive(Element<T>),
+    Negative(Element<T>),
+}
+
+/// A rolled [`Element`]: dice have already been cast into a [`Dice`] set, a
+/// bonus is carried through resolved to its final value.
+enum RolledElement<T: Rollable> {
+    Dice(Dice<T>),
+    Bonus(T),
+}
+
+/// A rolled [`Element`] together with the sign it carries.
+enum SignedRolledElement<T: Rollable> {
+    Positive(RolledElement<T>),
+    Negative(RolledElement<T>),
+}
+
+fn is_identifier(token: &str) -> bool {
+    !token.is_empty() && token.chars().all(|c| c.is_alphabetic() || c == '_')
+}
+
+/// A parsed dice expression, e.g. `3d6+2`, `1d20-1d4+5`, or `str+1d20`.
+///
+/// An `Expression` only holds the *parsed* terms; no dice are rolled until
+/// [`Expression::roll`] or [`Expression::eval_with_context`] is called.
+///
+/// # Examples
+///
+/// ```
+/// use one_d_six::Expression;
+///
+/// let expression: Expression = "3d6+2".parse().unwrap();
+/// let roll = expression.roll().unwrap();
+///
+/// assert!(roll.total() >= 5);
+/// assert!(roll.total() <= 20);
+/// ```
+///
+/// ## Negative terms
+///
+/// ```
+/// use one_d_six::Expression;
+///
+/// // 1d20 - 1d4 + 5
+/// let expression: Expression = "1d20-1d4+5".parse().unwrap();
+/// let roll = expression.roll().unwrap();
+///
+/// assert!(roll.total() >= 2);
+/// assert!(roll.total() <= 24);
+/// ```
+///
+/// ## Single dice token (degenerate case)
+///
+/// ```
+/// use one_d_six::Expression;
+///
+/// let expression: Expression = "1d6".parse().unwrap();
+/// let roll = expression.roll().unwrap();
+///
+/// assert!(roll.total() >= 1);
+/// assert!(roll.total() <= 6);
+/// ```
+///
+/// ## Named variables
+///
+/// ```
+/// use one_d_six::{Context, Expression};
+///
+/// let mut context = Context::new();
+/// context.insert("str", 3);
+///
+/// let expression: Expression = "str+1d20".parse().unwrap();
+/// let roll = expression.eval_with_context(&context).unwrap();
+///
+/// assert!(roll.total() >= 4);
+/// assert!(roll.total() <= 23);
+/// ```
+///
+/// ## Keep/drop modifiers
+///
+/// ```
+/// use one_d_six::Expression;
+///
+/// // Roll 4d6, dropping the lowest, for ability score generation
+/// let expression: Expression = "4d6dl1".parse().unwrap();
+/// let roll = expression.roll().unwrap();
+///
+/// assert!(roll.total() >= 3);
+/// assert!(roll.total() <= 18);
+/// ```
+pub struct Expression<T: Rollable = u32> {
+    terms: Vec<SignedElement<T>>,
+}
+
+impl<T: Rollable> FromStr for Expression<T>
+where
+    T: FromStr,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cleaned: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        let mut chars = cleaned.chars().peekable();
+
+        let mut tokens: Vec<(bool, String)> = Vec::new();
+        // A leading `+`/`-` sets the first term's sign rather than being
+        // mistaken for a missing-operand error below, so e.g. `-1d4+5` parses
+        // as a negative first term instead of failing outright.
+        let mut negative = match chars.peek() {
+            Some('-') => {
+                chars.next();
+                true
+            }
+            Some('+') => {
+                chars.next();
+                false
+            }
+            _ => false,
+        };
+        let mut buf = String::new();
+        for c in chars {
+            if c == '+' || c == '-' {
+                if buf.is_empty() {
+                    return Err(Error::ImproperFormat);
+                }
+                tokens.push((negative, buf.clone()));
+                buf.clear();
+                negative = c == '-';
+            } else {
+                buf.push(c);
+            }
+        }
+        if buf.is_empty() {
+            return Err(Error::ImproperFormat);
+        }
+        tokens.push((negative, buf));
+
+        let mut terms = Vec::with_capacity(tokens.len());
+        for (negative, token) in tokens {
+            let element = parse_element::<T>(&token)?;
+            terms.push(if negative {
+                SignedElement::Negative(element)
+            } else {
+                SignedElement::Positive(element)
+            });
+        }
+
+        Ok(Expression { terms })
+    }
+}
+
+/// Finds the `d` that separates a dice count from its faces, e.g. the `d` in
+/// `4d6` or `proficiencyd6` (the `proficiency d6` token once whitespace is
+/// stripped). Unlike a plain [`str::find`], this only matches a `d`
+/// immediately followed by a digit, so a bare variable name that happens to
+/// contain the letter "d" (e.g. `dex`) isn't mistaken for a dice separator.
+fn find_dice_separator(token: &str) -> Option<usize> {
+    let bytes = token.as_bytes();
+    (0..bytes.len())
+        .find(|&i| bytes[i] == b'd' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit))
+}
+
+fn parse_element<T: Rollable + FromStr>(token: &str) -> Result<Element<T>, Error> {
+    if is_identifier(token) {
+        return Ok(Element::Bonus(Value::Variable(token.to_string())));
+    }
+
+    if let Some(d_index) = find_dice_separator(token) {
+        let count_str = &token[..d_index];
+        let rest = &token[d_index + 1..];
+
+        if count_str.is_empty() {
+            return Err(Error::ImproperFormat);
+        }
+
+        let count = if let Ok(n) = count_str.parse::<usize>() {
+            CountValue::Literal(n)
+        } else if is_identifier(count_str) {
+            CountValue::Variable(count_str.to_string())
+        } else {
+            return Err(Error::ImproperFormat);
+        };
+
+        let (faces_str, suffix) = split_leading_digits(rest);
+        let faces: T = faces_str.parse().map_err(|_| Error::ImproperFormat)?;
+        let modifier = parse_modifier_suffix(suffix)?;
+
+        Ok(Element::Dice(count, faces, modifier))
+    } else if let Ok(value) = token.parse::<T>() {
+        Ok(Element::Bonus(Value::Literal(value)))
+    } else {
+        Err(Error::ImproperFormat)
+    }
+}
+
+fn resolve_count(count: &CountValue, context: &Context) -> Result<usize, Error> {
+    match count {
+        CountValue::Literal(n) => Ok(*n),
+        CountValue::Variable(name) => context
+            .get(name)
+            .map(|value| value.max(0) as usize)
+            .ok_or_else(|| Error::VariableNotFound(name.clone())),
+    }
+}
+
+fn resolve_value<T: Rollable + FromTotal>(value: &Value<T>, context: &Context) -> Result<T, Error> {
+    match value {
+        Value::Literal(value) => Ok(*value),
+        Value::Variable(name) => context
+            .get(name)
+            .map(T::from_total)
+            .ok_or_else(|| Error::VariableNotFound(name.clone())),
+    }
+}
+
+impl<T: Rollable> Expression<T> {
+    /// Rolls the dice in this expression and totals the result. Fails if
+    /// the expression references a variable, since there's no [`Context`]
+    /// to resolve it against; use [`Expression::eval_with_context`]
+    /// instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use one_d_six::Expression;
+    ///
+    /// let expression: Expression = "2d8+1d6-1".parse().unwrap();
+    /// let roll = expression.roll().unwrap();
+    ///
+    /// assert!(roll.total() >= 2);
+    /// assert!(roll.total() <= 21);
+    /// ```
+    pub fn roll(&self) -> Result<Roll<T>, Error>
+    where
+        T: IntoTotal + FromTotal + crate::DiceTotal<T> + Ord,
+    {
+        self.roll_with(&mut rand::thread_rng())
+    }
+
+    /// Rolls the dice in this expression using the given RNG source and
+    /// totals the result. Fails if the expression references a variable;
+    /// use [`Expression::eval_with_context_with`] instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use one_d_six::Expression;
+    /// use rand::SeedableRng;
+    /// use rand::rngs::StdRng;
+    ///
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// let expression: Expression = "2d8+1d6-1".parse().unwrap();
+    /// let roll = expression.roll_with(&mut rng).unwrap();
+    ///
+    /// assert!(roll.total() >= 2);
+    /// assert!(roll.total() <= 21);
+    /// ```
+    pub fn roll_with<R: Rng>(&self, rng: &mut R) -> Result<Roll<T>, Error>
+    where
+        T: IntoTotal + FromTotal + crate::DiceTotal<T> + Ord,
+    {
+        self.eval_with_context_with(&Context::new(), rng)
+    }
+
+    /// Rolls the dice in this expression, resolving any named variables
+    /// from `context`, and totals the result.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use one_d_six::{Context, Expression};
+    ///
+    /// let mut context = Context::new();
+    /// context.insert("proficiency", 2);
+    ///
+    /// let expression: Expression = "proficiency d6".parse().unwrap();
+    /// let roll = expression.eval_with_context(&context).unwrap();
+    ///
+    /// assert!(roll.total() >= 2);
+    /// assert!(roll.total() <= 12);
+    /// ```
+    ///
+    /// ## A negative variable
+    ///
+    /// A negative context value feeding an unsigned `Bonus` term is clamped
+    /// to `0` rather than wrapping around, matching how a negative dice
+    /// count is clamped by [`resolve_count`].
+    ///
+    /// ```
+    /// use one_d_six::{Context, Expression};
+    ///
+    /// let mut context = Context::new();
+    /// context.insert("str", -2);
+    ///
+    /// let expression: Expression = "str+1d20".parse().unwrap();
+    /// let roll = expression.eval_with_context(&context).unwrap();
+    ///
+    /// assert!(roll.total() >= 1);
+    /// assert!(roll.total() <= 20);
+    /// ```
+    pub fn eval_with_context(&self, context: &Context) -> Result<Roll<T>, Error>
+    where
+        T: IntoTotal + FromTotal + crate::DiceTotal<T> + Ord,
+    {
+        self.eval_with_context_with(context, &mut rand::thread_rng())
+    }
+
+    /// Rolls the dice in this expression using the given RNG source,
+    /// resolving any named variables from `context`, and totals the
+    /// result.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use one_d_six::{Context, Expression};
+    /// use rand::SeedableRng;
+    /// use rand::rngs::StdRng;
+    ///
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// let mut context = Context::new();
+    /// context.insert("proficiency", 2);
+    ///
+    /// let expression: Expression = "proficiency d6".parse().unwrap();
+    /// let roll = expression.eval_with_context_with(&context, &mut rng).unwrap();
+    ///
+    /// assert!(roll.total() >= 2);
+    /// assert!(roll.total() <= 12);
+    /// ```
+    pub fn eval_with_context_with<R: Rng>(
+        &self,
+        context: &Context,
+        rng: &mut R,
+    ) -> Result<Roll<T>, Error>
+    where
+        T: IntoTotal + FromTotal + crate::DiceTotal<T> + Ord,
+    {
+        let mut terms = Vec::with_capacity(self.terms.len());
+        let mut total: i64 = 0;
+
+        for term in &self.terms {
+            let (negative, element) = match term {
+                SignedElement::Positive(element) => (false, element),
+                SignedElement::Negative(element) => (true, element),
+            };
+
+            let rolled = match element {
+                Element::Dice(count, faces, modifier) => {
+                    let count = resolve_count(count, context)?;
+                    let mut dice = Dice::new_with(count, *faces, rng);
+                    if let Some(modifier) = modifier {
+                        dice.apply_modifier(*modifier);
+                    }
+                    let sum = dice.total().into_total();
+                    total += if negative { -sum } else { sum };
+                    RolledElement::Dice(dice)
+                }
+                Element::Bonus(value) => {
+                    let value = resolve_value(value, context)?;
+                    let sum = value.into_total();
+                    total += if negative { -sum } else { sum };
+                    RolledElement::Bonus(value)
+                }
+            };
+
+            terms.push(if negative {
+                SignedRolledElement::Negative(rolled)
+            } else {
+                SignedRolledElement::Positive(rolled)
+            });
+        }
+
+        Ok(Roll { terms, total })
+    }
+}
+
+/// The result of rolling an [`Expression`]: the per-term faces rolled and
+/// the final signed total.
+///
+/// # Example
+///
+/// ```
+/// use one_d_six::Expression;
+///
+/// let expression: Expression = "1d20+5".parse().unwrap();
+/// let roll = expression.roll().unwrap();
+///
+/// println!("1d20+5: {:?}", roll);
+/// ```
+pub struct Roll<T: Rollable = u32> {
+    terms: Vec<SignedRolledElement<T>>,
+    total: i64,
+}
+
+impl<T: Rollable> Roll<T> {
+    /// The summed, signed total of the roll.
+    pub fn total(&self) -> i64 {
+        self.total
+    }
+}
+
+impl<T: Rollable> fmt::Display for Roll<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.total)
+    }
+}
+
+impl<T: Rollable> fmt::Debug for Roll<T>
+where
+    T: fmt::Display,
+    T: Ord,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, term) in self.terms.iter().enumerate() {
+            let (negative, element) = match term {
+                SignedRolledElement::Positive(element) => (false, element),
+                SignedRolledElement::Negative(element) => (true, element),
+            };
+
+            if i == 0 {
+                if negative {
+                    write!(f, "-")?;
+                }
+            } else {
+                write!(f, " {} ", if negative { "-" } else { "+" })?;
+            }
+
+            match element {
+                // Delegates to `Dice`'s own `Debug` impl so a keep/drop
+                // modifier's dropped faces are marked here too, instead of
+                // printing a raw face list that doesn't agree with `total`.
+                RolledElement::Dice(dice) => write!(f, "[{:?}]", dice)?,
+                RolledElement::Bonus(value) => write!(f, "{}", value)?,
+            }
+        }
+        write!(f, " = {}", self.total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_dice_separator_requires_a_following_digit() {
+        assert_eq!(find_dice_separator("4d6"), Some(1));
+        assert_eq!(find_dice_separator("proficiencyd6"), Some(11));
+        assert_eq!(find_dice_separator("dex"), None);
+        assert_eq!(find_dice_separator("d6"), Some(0));
+    }
+
+    #[test]
+    fn is_identifier_accepts_only_alphabetic_and_underscore() {
+        assert!(is_identifier("str"));
+        assert!(is_identifier("proficiency_bonus"));
+        assert!(!is_identifier(""));
+        assert!(!is_identifier("d6"));
+        assert!(!is_identifier("str1"));
+    }
+
+    #[test]
+    fn parse_element_reads_a_literal_bonus() {
+        let element = parse_element::<u32>("5").unwrap();
+        assert!(matches!(element, Element::Bonus(Value::Literal(5))));
+    }
+
+    #[test]
+    fn parse_element_reads_a_variable_bonus() {
+        let element = parse_element::<u32>("str").unwrap();
+        assert!(matches!(element, Element::Bonus(Value::Variable(name)) if name == "str"));
+    }
+
+    #[test]
+    fn parse_element_reads_dice_with_a_literal_count() {
+        let element = parse_element::<u32>("3d6").unwrap();
+        assert!(matches!(
+            element,
+            Element::Dice(CountValue::Literal(3), 6, None)
+        ));
+    }
+
+    #[test]
+    fn parse_element_reads_dice_with_a_variable_count() {
+        let element = parse_element::<u32>("proficiencyd6").unwrap();
+        assert!(matches!(
+            element,
+            Element::Dice(CountValue::Variable(name), 6, None) if name == "proficiency"
+        ));
+    }
+
+    #[test]
+    fn parse_element_reads_dice_with_a_keep_drop_modifier() {
+        let element = parse_element::<u32>("4d6dl1").unwrap();
+        assert!(matches!(
+            element,
+            Element::Dice(CountValue::Literal(4), 6, Some(KeepDropModifier::DropLowest(1)))
+        ));
+    }
+
+    #[test]
+    fn parse_element_rejects_an_empty_dice_count() {
+        assert_eq!(parse_element::<u32>("d6").unwrap_err(), Error::ImproperFormat);
+    }
+
+    #[test]
+    fn parse_element_rejects_a_malformed_count() {
+        assert_eq!(parse_element::<u32>("3.5d6").unwrap_err(), Error::ImproperFormat);
+    }
+
+    #[test]
+    fn parse_element_rejects_unparseable_faces() {
+        assert_eq!(parse_element::<u32>("3dx").unwrap_err(), Error::ImproperFormat);
+    }
+
+    #[test]
+    fn parse_element_rejects_an_unparseable_token() {
+        assert_eq!(parse_element::<u32>("3.5").unwrap_err(), Error::ImproperFormat);
+    }
+
+    #[test]
+    fn resolve_count_clamps_a_negative_variable_to_zero() {
+        let mut context = Context::new();
+        context.insert("penalty", -3);
+
+        let count = resolve_count(&CountValue::Variable("penalty".to_string()), &context).unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn resolve_count_reports_an_unknown_variable() {
+        let context = Context::new();
+
+        let err = resolve_count(&CountValue::Variable("missing".to_string()), &context).unwrap_err();
+        assert_eq!(err, Error::VariableNotFound("missing".to_string()));
+    }
+
+    #[test]
+    fn resolve_value_clamps_a_negative_variable_to_zero() {
+        let mut context = Context::new();
+        context.insert("str", -2);
+
+        let value: u32 = resolve_value(&Value::Variable("str".to_string()), &context).unwrap();
+        assert_eq!(value, 0);
+    }
+
+    #[test]
+    fn resolve_value_reports_an_unknown_variable() {
+        let context = Context::new();
+
+        let err =
+            resolve_value::<u32>(&Value::Variable("missing".to_string()), &context).unwrap_err();
+        assert_eq!(err, Error::VariableNotFound("missing".to_string()));
+    }
+
+    #[test]
+    fn from_str_parses_a_leading_negative_sign() {
+        let expression: Expression = "-1d4+5".parse().unwrap();
+        assert!(matches!(expression.terms[0], SignedElement::Negative(_)));
+        assert!(matches!(expression.terms[1], SignedElement::Positive(_)));
+    }
+
+    #[test]
+    fn from_str_parses_a_leading_explicit_positive_sign() {
+        let expression: Expression = "+1d6".parse().unwrap();
+        assert!(matches!(expression.terms[0], SignedElement::Positive(_)));
+    }
+
+    #[test]
+    fn from_str_rejects_a_double_operator() {
+        let result: Result<Expression, Error> = "1d6++2".parse();
+        assert!(matches!(result, Err(Error::ImproperFormat)));
+    }
+
+    #[test]
+    fn from_str_rejects_an_empty_expression() {
+        let result: Result<Expression, Error> = "".parse();
+        assert!(matches!(result, Err(Error::ImproperFormat)));
+    }
+}
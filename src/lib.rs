@@ -54,16 +54,52 @@
 //! let dice = Dice::new(3, 6);
 //! println!("3d6: {:?}", dice);
 //! ```
+//!
+//! ## Full Expressions
+//!
+//! ```
+//! use one_d_six::Expression;
+//!
+//! // 1d20 - 1d4 + 5
+//! let expression: Expression = "1d20-1d4+5".parse().unwrap();
+//! let roll = expression.roll().unwrap();
+//!
+//! println!("1d20-1d4+5: {:?}", roll);
+//! ```
+//!
+//! ## Named Variables
+//!
+//! ```
+//! use one_d_six::{Context, Expression};
+//!
+//! let mut context = Context::new();
+//! context.insert("str", 3);
+//!
+//! let expression: Expression = "str+1d20".parse().unwrap();
+//! let roll = expression.eval_with_context(&context).unwrap();
+//!
+//! println!("str+1d20: {:?}", roll);
+//! ```
 use std::str::FromStr;
 
+pub use context::*;
 pub use dice::*;
+pub use dice_pool::*;
 pub use dice_total::*;
 pub use die::*;
+pub use error::*;
+pub use expression::*;
+pub use percentile::*;
 pub use rollable::*;
 
+mod context;
 mod dice;
+mod dice_pool;
 mod dice_total;
 mod die;
+mod error;
+mod expression;
+mod percentile;
 mod rollable;
 
 /// Attempts to roll dice based on a *1d6* style string.
@@ -80,15 +116,120 @@ mod rollable;
 ///     unreachable!();
 /// }
 /// ```
-pub fn try_quickroll<T: Rollable>(dice_format: &str) -> Result<T, String>
+pub fn try_quickroll<T: Rollable>(dice_format: &str) -> Result<T, Error>
 where
     T: DiceTotal<T>,
     T: FromStr,
+    T: Ord,
 {
     let dice: Dice<T> = dice_format.parse()?;
     Ok(dice.total())
 }
 
+/// Attempts to roll dice based on a *1d6* style string, using the given RNG
+/// source.
+///
+/// # Example
+///
+/// ```
+/// use one_d_six::try_quickroll_with;
+/// use rand::SeedableRng;
+/// use rand::rngs::StdRng;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+///
+/// if let Ok(roll) = try_quickroll_with::<u32, _>("1d6", &mut rng) {
+///     assert!(roll >= 1);
+///     assert!(roll <= 6);
+/// } else {
+///     unreachable!();
+/// }
+/// ```
+pub fn try_quickroll_with<T: Rollable, R: rand::Rng>(
+    dice_format: &str,
+    rng: &mut R,
+) -> Result<T, Error>
+where
+    T: DiceTotal<T>,
+    T: FromStr,
+    T: Ord,
+{
+    let dice: Dice<T> = Dice::from_str_with(dice_format, rng)?;
+    Ok(dice.total())
+}
+
+/// Attempts to roll a full dice [`Expression`], resolving any named
+/// variables from `context`.
+///
+/// # Example
+///
+/// ```
+/// use one_d_six::{try_quickroll_with_context, Context};
+///
+/// let mut context = Context::new();
+/// context.insert("str", 3);
+///
+/// if let Ok(roll) = try_quickroll_with_context::<u32>("str+1d20", &context) {
+///     assert!(roll >= 4);
+///     assert!(roll <= 23);
+/// } else {
+///     unreachable!();
+/// }
+/// ```
+pub fn try_quickroll_with_context<T: Rollable>(
+    dice_format: &str,
+    context: &Context,
+) -> Result<T, Error>
+where
+    T: FromStr,
+    T: IntoTotal,
+    T: FromTotal,
+    T: DiceTotal<T>,
+    T: Ord,
+{
+    let expression: Expression<T> = dice_format.parse()?;
+    let roll = expression.eval_with_context(context)?;
+    Ok(T::from_total(roll.total()))
+}
+
+/// Attempts to roll a full dice [`Expression`], resolving any named
+/// variables from `context`, using the given RNG source.
+///
+/// # Example
+///
+/// ```
+/// use one_d_six::{try_quickroll_with_context_with, Context};
+/// use rand::SeedableRng;
+/// use rand::rngs::StdRng;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let mut context = Context::new();
+/// context.insert("str", 3);
+///
+/// if let Ok(roll) = try_quickroll_with_context_with::<u32, _>("str+1d20", &context, &mut rng) {
+///     assert!(roll >= 4);
+///     assert!(roll <= 23);
+/// } else {
+///     unreachable!();
+/// }
+/// ```
+pub fn try_quickroll_with_context_with<T: Rollable, R: rand::Rng>(
+    dice_format: &str,
+    context: &Context,
+    rng: &mut R,
+) -> Result<T, Error>
+where
+    T: FromStr,
+    T: IntoTotal,
+    T: FromTotal,
+    T: DiceTotal<T>,
+    T: Ord,
+{
+    let expression: Expression<T> = dice_format.parse()?;
+    let roll = expression.eval_with_context_with(context, rng)?;
+    Ok(T::from_total(roll.total()))
+}
+
 /// Rolls dice based on a *1d6* style string.
 ///
 /// # Example
@@ -108,7 +249,36 @@ pub fn quickroll<T: Rollable>(dice_format: &str) -> T
 where
     T: DiceTotal<T>,
     T: FromStr,
+    T: Ord,
 {
     let dice: Dice<T> = dice_format.parse().unwrap();
     dice.total()
 }
+
+/// Rolls dice based on a *1d6* style string, using the given RNG source.
+///
+/// # Example
+///
+/// ```
+/// use one_d_six::quickroll_with;
+/// use rand::SeedableRng;
+/// use rand::rngs::StdRng;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let coin_flip: u8 = quickroll_with("1d2", &mut rng);
+///
+/// assert!(coin_flip == 1 || coin_flip == 2);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `dice_format` is in an improper format.
+pub fn quickroll_with<T: Rollable, R: rand::Rng>(dice_format: &str, rng: &mut R) -> T
+where
+    T: DiceTotal<T>,
+    T: FromStr,
+    T: Ord,
+{
+    let dice: Dice<T> = Dice::from_str_with(dice_format, rng).unwrap();
+    dice.total()
+}
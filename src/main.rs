@@ -3,43 +3,140 @@ use clap::{
     App,
 };
 
-use one_d_six::Dice;
+use one_d_six::{
+    DicePool,
+    DicePoolQuality,
+    Expression,
+    PercentileRoll,
+};
+
+/// Parses an `NdM` token (e.g. `4d10`) into its dice count and face count,
+/// for use with `--pool`.
+fn parse_pool_token(token: &str) -> Option<(usize, u32)> {
+    let d_index = token.find('d')?;
+    let count: usize = token[..d_index].parse().ok()?;
+    let faces: u32 = token[d_index + 1..].parse().ok()?;
+    Some((count, faces))
+}
 
 fn main() {
     let input_arg = Arg::with_name("DICE")
-        .help("The dice to be rolled (e.g. 1d6)")
-        .required(true)
+        .help("The dice to be rolled (e.g. 1d6), or an NdM dice pool when --pool is given")
         .index(1)
-        .min_values(1);
+        .min_values(1)
+        .required_unless("percentile");
     let complexity_arg = Arg::with_name("complexity")
         .short("c")
         .long("complex")
         .help("If you want each cast die to be printed");
+    let percentile_arg = Arg::with_name("percentile")
+        .short("p")
+        .long("percentile")
+        .help("Rolls a Call of Cthulhu-style percentile check against SKILL instead of parsing DICE as an expression")
+        .takes_value(true)
+        .value_name("SKILL")
+        .conflicts_with("DICE");
+    let pool_arg = Arg::with_name("pool")
+        .long("pool")
+        .help("Scores each DICE (e.g. 4d10) as a dice pool, counting successes at or above SUCCESS_THRESHOLD instead of summing faces")
+        .takes_value(true)
+        .value_name("SUCCESS_THRESHOLD")
+        .requires("DICE");
+    let quality_arg = Arg::with_name("quality")
+        .long("quality")
+        .help("The DicePoolQuality a --pool roll is scored with: standard, nine-again, eight-again, ten-again, or rote (default: standard)")
+        .takes_value(true)
+        .requires("pool");
     let app_args = App::new("One D Six")
         .version("0.1.0")
         .about("Rolls some dice")
         .arg(input_arg)
-        .arg(complexity_arg);
+        .arg(complexity_arg)
+        .arg(percentile_arg)
+        .arg(pool_arg)
+        .arg(quality_arg);
     let matches = app_args.get_matches();
-    let rolls = matches.values_of("DICE").unwrap();
     let complex = matches.is_present("complexity");
 
+    if let Some(skill) = matches.value_of("percentile") {
+        match skill.parse::<u8>() {
+            Ok(skill) => {
+                let roll = PercentileRoll::new(skill);
+                if complex {
+                    println!(
+                        "{}: tens {:?}, units {} -> {} ({:?})",
+                        skill,
+                        roll.tens_dice(),
+                        roll.units_die(),
+                        roll.roll(),
+                        roll.tier(),
+                    );
+                } else {
+                    println!("{}: {} ({:?})", skill, roll.roll(), roll.tier());
+                }
+            }
+            Err(_) => eprintln!("{}: not a valid skill value", skill),
+        }
+        return;
+    }
+
+    let rolls = matches.values_of("DICE").unwrap();
+
+    if let Some(threshold) = matches.value_of("pool") {
+        let quality = match matches.value_of("quality").unwrap_or("standard") {
+            "nine-again" => DicePoolQuality::NineAgain,
+            "eight-again" => DicePoolQuality::EightAgain,
+            "ten-again" => DicePoolQuality::TenAgain,
+            "rote" => DicePoolQuality::Rote,
+            _ => DicePoolQuality::Standard,
+        };
+        let threshold: u32 = match threshold.parse() {
+            Ok(threshold) => threshold,
+            Err(_) => {
+                eprintln!("{}: not a valid success threshold", threshold);
+                return;
+            }
+        };
+
+        for roll in rolls {
+            match parse_pool_token(roll) {
+                Some((count, faces)) => {
+                    let pool = DicePool::new(count, faces);
+                    let result = pool.count_successes(threshold, quality);
+                    if complex {
+                        println!(
+                            "{}: {:?} -> {} successes ({:?})",
+                            roll,
+                            result.faces(),
+                            result.successes(),
+                            result.outcome(),
+                        );
+                    } else {
+                        println!("{}: {} successes", roll, result.successes());
+                    }
+                }
+                None => eprintln!("{}: not a valid NdM dice pool", roll),
+            }
+        }
+        return;
+    }
+
     let rolls = rolls.map(|r| {
-        let dice: Result<Dice<u32>, _> = r.parse();
-        (r, dice)
+        let expression: Result<Expression<u32>, _> = r.parse();
+        (r, expression)
     });
 
     if complex {
         for (roll, result) in rolls {
-            match result {
-                Ok(dice) => println!("{}: {:?}", roll, dice),
+            match result.and_then(|expression| expression.roll()) {
+                Ok(rolled) => println!("{}: {:?}", roll, rolled),
                 Err(e) => eprintln!("{}: {}", roll, e),
             };
         }
     } else {
         for (roll, result) in rolls {
-            match result {
-                Ok(dice) => println!("{}: {}", roll, dice),
+            match result.and_then(|expression| expression.roll()) {
+                Ok(rolled) => println!("{}: {}", roll, rolled),
                 Err(e) => eprintln!("{}: {}", roll, e),
             };
         }
@@ -0,0 +1,264 @@
+use rand::Rng;
+
+use crate::Die;
+
+/// Rolls a single tens-or-units digit die using the given RNG source: a d10
+/// showing 0-9 (a face of 10 is read as 0).
+fn roll_digit_with<R: Rng>(rng: &mut R) -> u8 {
+    let face = Die::new_with(10u8, rng).current_face();
+    if face == 10 {
+        0
+    } else {
+        face
+    }
+}
+
+/// Which extra tens dice are rolled, and which one is kept.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TensModifier {
+    None,
+    /// Roll `n` extra tens dice and keep the lowest.
+    Bonus(usize),
+    /// Roll `n` extra tens dice and keep the highest.
+    Penalty(usize),
+}
+
+/// The outcome tier of a [`PercentileRoll`], from worst to best.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PercentileTier {
+    Fumble,
+    Failure,
+    RegularSuccess,
+    HardSuccess,
+    ExtremeSuccess,
+    Critical,
+}
+
+fn classify(roll: u8, skill: u8) -> PercentileTier {
+    if roll == 1 {
+        return PercentileTier::Critical;
+    }
+
+    let fumble_floor = if skill < 50 { 96 } else { 100 };
+    if roll >= fumble_floor {
+        return PercentileTier::Fumble;
+    }
+    if roll > skill {
+        return PercentileTier::Failure;
+    }
+    if roll <= skill / 5 {
+        return PercentileTier::ExtremeSuccess;
+    }
+    if roll <= skill / 2 {
+        return PercentileTier::HardSuccess;
+    }
+    PercentileTier::RegularSuccess
+}
+
+/// A Call-of-Cthulhu-style percentile roll against a skill value.
+///
+/// # Examples
+///
+/// ```
+/// use one_d_six::PercentileRoll;
+///
+/// let roll = PercentileRoll::new(65);
+///
+/// assert!(roll.roll() >= 1);
+/// assert!(roll.roll() <= 100);
+/// ```
+///
+/// ## With a bonus die
+///
+/// ```
+/// use one_d_six::PercentileRoll;
+///
+/// // Roll 1 extra tens die, keeping the lowest tens digit.
+/// let roll = PercentileRoll::with_bonus_dice(65, 1);
+///
+/// assert_eq!(roll.tens_dice().len(), 2);
+/// ```
+pub struct PercentileRoll {
+    skill: u8,
+    roll: u8,
+    tier: PercentileTier,
+    tens_dice: Vec<u8>,
+    units_die: u8,
+}
+
+impl PercentileRoll {
+    /// Rolls a plain percentile roll against `skill`.
+    pub fn new(skill: u8) -> Self {
+        Self::roll_with_modifier(skill, TensModifier::None, &mut rand::thread_rng())
+    }
+
+    /// Rolls a plain percentile roll against `skill`, using the given RNG
+    /// source.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use one_d_six::PercentileRoll;
+    /// use rand::SeedableRng;
+    /// use rand::rngs::StdRng;
+    ///
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// let roll = PercentileRoll::new_with(65, &mut rng);
+    ///
+    /// assert!(roll.roll() >= 1);
+    /// assert!(roll.roll() <= 100);
+    /// ```
+    pub fn new_with<R: Rng>(skill: u8, rng: &mut R) -> Self {
+        Self::roll_with_modifier(skill, TensModifier::None, rng)
+    }
+
+    /// Rolls with `bonus_dice` extra tens dice, keeping the lowest tens
+    /// digit (the standard Call of Cthulhu bonus die mechanic).
+    pub fn with_bonus_dice(skill: u8, bonus_dice: usize) -> Self {
+        Self::roll_with_modifier(skill, TensModifier::Bonus(bonus_dice), &mut rand::thread_rng())
+    }
+
+    /// Rolls with `bonus_dice` extra tens dice, keeping the lowest tens
+    /// digit, using the given RNG source.
+    pub fn with_bonus_dice_with<R: Rng>(skill: u8, bonus_dice: usize, rng: &mut R) -> Self {
+        Self::roll_with_modifier(skill, TensModifier::Bonus(bonus_dice), rng)
+    }
+
+    /// Rolls with `penalty_dice` extra tens dice, keeping the highest tens
+    /// digit (the standard Call of Cthulhu penalty die mechanic).
+    pub fn with_penalty_dice(skill: u8, penalty_dice: usize) -> Self {
+        Self::roll_with_modifier(
+            skill,
+            TensModifier::Penalty(penalty_dice),
+            &mut rand::thread_rng(),
+        )
+    }
+
+    /// Rolls with `penalty_dice` extra tens dice, keeping the highest tens
+    /// digit, using the given RNG source.
+    pub fn with_penalty_dice_with<R: Rng>(skill: u8, penalty_dice: usize, rng: &mut R) -> Self {
+        Self::roll_with_modifier(skill, TensModifier::Penalty(penalty_dice), rng)
+    }
+
+    fn roll_with_modifier<R: Rng>(skill: u8, modifier: TensModifier, rng: &mut R) -> Self {
+        let units_die = roll_digit_with(rng);
+
+        let extra = match modifier {
+            TensModifier::None => 0,
+            TensModifier::Bonus(n) => n,
+            TensModifier::Penalty(n) => n,
+        };
+
+        let mut tens_dice = Vec::with_capacity(1 + extra);
+        for _ in 0..=extra {
+            tens_dice.push(roll_digit_with(rng));
+        }
+
+        let tens_digit = match modifier {
+            TensModifier::None => tens_dice[0],
+            TensModifier::Bonus(_) => *tens_dice.iter().min().unwrap(),
+            TensModifier::Penalty(_) => *tens_dice.iter().max().unwrap(),
+        };
+
+        let roll = if tens_digit == 0 && units_die == 0 {
+            100
+        } else {
+            tens_digit * 10 + units_die
+        };
+
+        let tier = classify(roll, skill);
+
+        PercentileRoll {
+            skill,
+            roll,
+            tier,
+            tens_dice,
+            units_die,
+        }
+    }
+
+    /// The skill value this roll was made against.
+    pub fn skill(&self) -> u8 {
+        self.skill
+    }
+
+    /// The final roll, from 1 to 100.
+    pub fn roll(&self) -> u8 {
+        self.roll
+    }
+
+    /// The success tier this roll falls into.
+    pub fn tier(&self) -> PercentileTier {
+        self.tier
+    }
+
+    /// Every tens die that was rolled. Has more than one entry only when a
+    /// bonus or penalty die was applied.
+    pub fn tens_dice(&self) -> &[u8] {
+        &self.tens_dice
+    }
+
+    /// The units die (0-9) that was rolled.
+    pub fn units_die(&self) -> u8 {
+        self.units_die
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_critical_is_always_01() {
+        assert_eq!(classify(1, 1), PercentileTier::Critical);
+        assert_eq!(classify(1, 99), PercentileTier::Critical);
+    }
+
+    #[test]
+    fn classify_fumble_floor_depends_on_skill() {
+        assert_eq!(classify(96, 49), PercentileTier::Fumble);
+        assert_eq!(classify(96, 50), PercentileTier::Failure);
+        assert_eq!(classify(100, 99), PercentileTier::Fumble);
+    }
+
+    #[test]
+    fn classify_success_tiers() {
+        assert_eq!(classify(65, 50), PercentileTier::Failure);
+        assert_eq!(classify(50, 50), PercentileTier::RegularSuccess);
+        assert_eq!(classify(25, 50), PercentileTier::HardSuccess);
+        assert_eq!(classify(10, 50), PercentileTier::ExtremeSuccess);
+    }
+
+    /// A tens digit and units digit combine the same way
+    /// [`PercentileRoll::roll_with_modifier`] does: `00` and `0` together
+    /// read as the maximum roll of 100, rather than 0.
+    fn expected_roll(tens_digit: u8, units_die: u8) -> u8 {
+        if tens_digit == 0 && units_die == 0 {
+            100
+        } else {
+            tens_digit * 10 + units_die
+        }
+    }
+
+    #[test]
+    fn bonus_dice_keep_the_lowest_tens_digit() {
+        for _ in 0..100 {
+            let roll = PercentileRoll::with_bonus_dice(65, 2);
+
+            assert_eq!(roll.tens_dice().len(), 3);
+            let lowest = *roll.tens_dice().iter().min().unwrap();
+            assert_eq!(roll.roll(), expected_roll(lowest, roll.units_die()));
+        }
+    }
+
+    #[test]
+    fn penalty_dice_keep_the_highest_tens_digit() {
+        for _ in 0..100 {
+            let roll = PercentileRoll::with_penalty_dice(65, 2);
+
+            assert_eq!(roll.tens_dice().len(), 3);
+            let highest = *roll.tens_dice().iter().max().unwrap();
+            assert_eq!(roll.roll(), expected_roll(highest, roll.units_die()));
+        }
+    }
+}
@@ -21,8 +21,8 @@ use rand::Rng;
 ///
 /// impl Rollable for Shapes {
 ///     // We're ignoring max since we don't need a maximum for this example
-///     fn roll(_max: Shapes) -> Shapes {
-///         let roll_result: u8 = quickroll("1d3");
+///     fn roll_with<R: rand::Rng>(_max: Shapes, rng: &mut R) -> Shapes {
+///         let roll_result = rng.gen_range(0, 3) + 1;
 ///         match roll_result {
 ///             1 => Shapes::Triangle,
 ///             2 => Shapes::Square,
@@ -38,36 +38,67 @@ use rand::Rng;
 /// println!("You rolled {:?}!", shape_roller.roll());
 /// ```
 pub trait Rollable: Copy {
-    fn roll(max: Self) -> Self;
+    /// Rolls using the given RNG source.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use one_d_six::Rollable;
+    /// use rand::SeedableRng;
+    /// use rand::rngs::StdRng;
+    ///
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// let result = u32::roll_with(6, &mut rng);
+    ///
+    /// assert!(result >= 1);
+    /// assert!(result <= 6);
+    /// ```
+    fn roll_with<R: Rng>(max: Self, rng: &mut R) -> Self;
+
+    /// Rolls using `rand::thread_rng()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use one_d_six::Rollable;
+    ///
+    /// let result = u32::roll(6);
+    ///
+    /// assert!(result >= 1);
+    /// assert!(result <= 6);
+    /// ```
+    fn roll(max: Self) -> Self {
+        Self::roll_with(max, &mut rand::thread_rng())
+    }
 }
 
 impl Rollable for u8 {
-    fn roll(max: u8) -> u8 {
-        rand::thread_rng().gen_range(0, max) + 1
+    fn roll_with<R: Rng>(max: u8, rng: &mut R) -> u8 {
+        rng.gen_range(0, max) + 1
     }
 }
 impl Rollable for u16 {
-    fn roll(max: u16) -> u16 {
-        rand::thread_rng().gen_range(0, max) + 1
+    fn roll_with<R: Rng>(max: u16, rng: &mut R) -> u16 {
+        rng.gen_range(0, max) + 1
     }
 }
 impl Rollable for u32 {
-    fn roll(max: u32) -> u32 {
-        rand::thread_rng().gen_range(0, max) + 1
+    fn roll_with<R: Rng>(max: u32, rng: &mut R) -> u32 {
+        rng.gen_range(0, max) + 1
     }
 }
 impl Rollable for u64 {
-    fn roll(max: u64) -> u64 {
-        rand::thread_rng().gen_range(0, max) + 1
+    fn roll_with<R: Rng>(max: u64, rng: &mut R) -> u64 {
+        rng.gen_range(0, max) + 1
     }
 }
 impl Rollable for u128 {
-    fn roll(max: u128) -> u128 {
-        rand::thread_rng().gen_range(0, max) + 1
+    fn roll_with<R: Rng>(max: u128, rng: &mut R) -> u128 {
+        rng.gen_range(0, max) + 1
     }
 }
 impl Rollable for usize {
-    fn roll(max: usize) -> usize {
-        rand::thread_rng().gen_range(0, max) + 1
+    fn roll_with<R: Rng>(max: usize, rng: &mut R) -> usize {
+        rng.gen_range(0, max) + 1
     }
 }